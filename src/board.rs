@@ -0,0 +1,107 @@
+extern crate serde_json;
+
+use values::{Color, Block, GridPosition, Dimension, PositionedBlock};
+
+// Uppercase is a breaker (e.g. `B`), lowercase is a plain block (`b`); any
+// other character is an empty cell.
+fn block_from_char(c: char) -> Option<Block> {
+    let breaker = c.is_uppercase();
+    Color::from_char(c).map(|color| Block::new(color, breaker))
+}
+
+// Bottom row last, matching `GridPosition::offset`'s y-up convention; rows
+// align to the top of `dimension`.
+pub fn parse_board(text: &str, dimension: Dimension) -> Vec<PositionedBlock> {
+    let mut blocks = Vec::new();
+
+    for (row, line) in text.lines().enumerate() {
+        let y = dimension.h() as i8 - 1 - row as i8;
+        for (col, c) in line.chars().enumerate() {
+            if let Some(block) = block_from_char(c) {
+                let position = GridPosition::new(col as i8, y);
+                blocks.push(PositionedBlock::new(block, position));
+            }
+        }
+    }
+
+    blocks
+}
+
+// Entries look like `{"position": [x, y], "color": "B", "breaker": false}`.
+// Returns an error message instead of panicking on malformed level data.
+pub fn parse_board_json(text: &str) -> Result<Vec<PositionedBlock>, String> {
+    let value: serde_json::Value = serde_json::from_str(text)
+        .map_err(|e| format!("invalid JSON: {}", e))?;
+    let entries = value.as_array()
+        .ok_or_else(|| "expected a top-level JSON array of board entries".to_string())?;
+
+    entries.iter().map(|entry| {
+        let x = entry["position"][0].as_i64()
+            .ok_or_else(|| "entry is missing a numeric position[0]".to_string())? as i8;
+        let y = entry["position"][1].as_i64()
+            .ok_or_else(|| "entry is missing a numeric position[1]".to_string())? as i8;
+        let color_char = entry["color"].as_str().and_then(|s| s.chars().next())
+            .ok_or_else(|| "entry is missing a color letter".to_string())?;
+        let color = Color::from_char(color_char)
+            .ok_or_else(|| format!("unrecognized color letter '{}'", color_char))?;
+        let breaker = entry["breaker"].as_bool().unwrap_or(false);
+
+        let position = GridPosition::new(x, y);
+        let block = Block::new(color, breaker);
+        Ok(PositionedBlock::new(block, position))
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bottom_row_is_last_line_and_aligns_to_dimension_height() {
+        let text = "Rb\nbG";
+        let blocks = parse_board(text, Dimension::new(2, 2));
+
+        let at = |x: i8, y: i8| blocks.iter().find(|b| b.x() == x && b.y() == y).unwrap();
+
+        assert_eq!(at(0, 1).color(), Color::Red);
+        assert!(at(0, 1).breaker());
+        assert_eq!(at(1, 1).color(), Color::Blue);
+        assert!(!at(1, 1).breaker());
+        assert_eq!(at(0, 0).color(), Color::Blue);
+        assert_eq!(at(1, 0).color(), Color::Green);
+        assert_eq!(blocks.len(), 4);
+    }
+
+    #[test]
+    fn unrecognized_characters_are_skipped() {
+        let blocks = parse_board(" . \n", Dimension::new(3, 1));
+        assert!(blocks.is_empty());
+    }
+
+    #[test]
+    fn parses_json_entries() {
+        let json = r#"[
+            {"position": [0, 0], "color": "B", "breaker": true},
+            {"position": [1, 2], "color": "y"}
+        ]"#;
+        let blocks = parse_board_json(json).unwrap();
+
+        assert_eq!(blocks.len(), 2);
+        let first = blocks.iter().find(|b| b.x() == 0 && b.y() == 0).unwrap();
+        assert_eq!(first.color(), Color::Blue);
+        assert!(first.breaker());
+
+        let second = blocks.iter().find(|b| b.x() == 1 && b.y() == 2).unwrap();
+        assert_eq!(second.color(), Color::Yellow);
+        assert!(!second.breaker());
+    }
+
+    #[test]
+    fn reports_an_error_instead_of_panicking_on_malformed_json() {
+        assert!(parse_board_json("not json").is_err());
+        assert!(parse_board_json("{}").is_err());
+        assert!(parse_board_json(r#"[{"position": [0, 0], "color": 7}]"#).is_err());
+        assert!(parse_board_json(r#"[{"position": [0, 0], "color": "Q"}]"#).is_err());
+        assert!(parse_board_json(r#"[{"color": "B"}]"#).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::{HashMap, HashSet};
+
+use values::{Direction, GridPosition, PositionedBlock};
+
+// Expands from `start` over 4-neighbors sharing its color, using
+// `Direction::all()` and `GridPosition::offset` to walk the field. `start`
+// is included in the returned group.
+pub fn connected_group(start: PositionedBlock, field: &HashSet<PositionedBlock>) -> HashSet<PositionedBlock> {
+    let by_position: HashMap<GridPosition, PositionedBlock> =
+        field.iter().map(|&block| (block.position(), block)).collect();
+
+    let mut visited_positions = HashSet::new();
+    let mut group = HashSet::new();
+    let mut stack = vec![start];
+    visited_positions.insert(start.position());
+
+    while let Some(block) = stack.pop() {
+        group.insert(block);
+
+        for direction in Direction::all() {
+            let position = block.position().offset(direction);
+            if visited_positions.contains(&position) {
+                continue;
+            }
+
+            if let Some(&neighbor) = by_position.get(&position) {
+                if neighbor.color() == start.color() {
+                    visited_positions.insert(position);
+                    stack.push(neighbor);
+                }
+            }
+        }
+    }
+
+    group
+}
+
+// Floods every breaker's color group and unions the results, returning the
+// set of blocks to remove from the field. A group only clears because it is
+// reached from one of its own breakers, so it always contains one; groups
+// never cross color boundaries since `connected_group` only follows
+// same-color neighbors.
+pub fn resolve_clears(field: &HashSet<PositionedBlock>) -> HashSet<PositionedBlock> {
+    let mut cleared = HashSet::new();
+    let mut visited_positions = HashSet::new();
+
+    for &block in field {
+        if !block.breaker() || visited_positions.contains(&block.position()) {
+            continue;
+        }
+
+        let group = connected_group(block, field);
+        for &member in &group {
+            visited_positions.insert(member.position());
+            cleared.insert(member);
+        }
+    }
+
+    cleared
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use values::{Block, Color};
+
+    fn block(color: Color, breaker: bool, x: i8, y: i8) -> PositionedBlock {
+        PositionedBlock::new(Block::new(color, breaker), GridPosition::new(x, y))
+    }
+
+    #[test]
+    fn connected_group_stops_at_color_boundary() {
+        let mut field = HashSet::new();
+        let start = block(Color::Red, false, 0, 0);
+        field.insert(start);
+        field.insert(block(Color::Red, false, 1, 0));
+        field.insert(block(Color::Blue, false, 2, 0));
+
+        let group = connected_group(start, &field);
+
+        assert_eq!(group.len(), 2);
+        assert!(group.iter().all(|b| b.color() == Color::Red));
+    }
+
+    #[test]
+    fn resolve_clears_includes_the_whole_group_touched_by_a_breaker() {
+        let mut field = HashSet::new();
+        field.insert(block(Color::Green, true, 0, 0));
+        field.insert(block(Color::Green, false, 1, 0));
+        field.insert(block(Color::Green, false, 0, 1));
+        field.insert(block(Color::Blue, false, 5, 5));
+
+        let cleared = resolve_clears(&field);
+
+        assert_eq!(cleared.len(), 3);
+        assert!(cleared.iter().all(|b| b.color() == Color::Green));
+    }
+
+    #[test]
+    fn resolve_clears_ignores_groups_without_a_breaker() {
+        let mut field = HashSet::new();
+        field.insert(block(Color::Yellow, false, 0, 0));
+        field.insert(block(Color::Yellow, false, 1, 0));
+
+        let cleared = resolve_clears(&field);
+
+        assert!(cleared.is_empty());
+    }
+}
@@ -0,0 +1,143 @@
+extern crate rand;
+
+use self::rand::{Rng, SeedableRng, StdRng};
+
+use values::{Color, Block, Piece, GridPosition, Direction};
+
+// One unit of randomness drawn from the generator. Recording these lets a
+// reloaded generator fast-forward back to the exact point it was saved at,
+// instead of restarting from the seed and handing out again the pieces that
+// were already placed.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GeneratorOp {
+    Color,
+    Piece,
+}
+
+// Generates pieces and colors from a seeded RNG, for reproducible matches.
+pub struct PieceGenerator {
+    seed: u64,
+    rng: StdRng,
+    breaker_weight: u32,
+    history: Vec<GeneratorOp>,
+}
+
+impl PieceGenerator {
+    pub fn new(seed: u64) -> Self {
+        PieceGenerator {
+            seed: seed,
+            rng: StdRng::from_seed(&[seed as usize]),
+            breaker_weight: 4,
+            history: Vec::new(),
+        }
+    }
+
+    pub fn with_breaker_weight(seed: u64, breaker_weight: u32) -> Self {
+        PieceGenerator {
+            breaker_weight: breaker_weight,
+            ..PieceGenerator::new(seed)
+        }
+    }
+
+    // Rebuilds a generator at the exact point `history` was recorded, by
+    // replaying the same sequence of draws against a freshly seeded RNG.
+    pub fn replay(seed: u64, breaker_weight: u32, history: &[GeneratorOp]) -> Self {
+        let mut generator = PieceGenerator::with_breaker_weight(seed, breaker_weight);
+
+        for op in history {
+            match *op {
+                GeneratorOp::Color => { generator.next_color(); },
+                GeneratorOp::Piece => { generator.next_piece(0, 0); },
+            }
+        }
+
+        generator
+    }
+
+    pub fn seed(&self) -> u64 { self.seed }
+    pub fn breaker_weight(&self) -> u32 { self.breaker_weight }
+    pub fn history(&self) -> &[GeneratorOp] { &self.history }
+
+    fn draw_color(&mut self) -> Color {
+        let all = [
+            Color::Blue,
+            Color::Red,
+            Color::Green,
+            Color::Yellow
+        ];
+
+        *self.rng.choose(&all).unwrap()
+    }
+
+    pub fn next_color(&mut self) -> Color {
+        self.history.push(GeneratorOp::Color);
+        self.draw_color()
+    }
+
+    pub fn next_piece(&mut self, x: i8, y: i8) -> Piece {
+        self.history.push(GeneratorOp::Piece);
+
+        let position = GridPosition::new(x, y);
+        let block1 = Block::new(self.draw_color(), self.rng.gen_weighted_bool(self.breaker_weight));
+        let block2 = Block::new(self.draw_color(), self.rng.gen_weighted_bool(self.breaker_weight));
+
+        Piece {
+            blocks: [block1, block2],
+            position: position,
+            direction: Direction::Up,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_produces_the_same_color_stream() {
+        let mut a = PieceGenerator::new(7);
+        let mut b = PieceGenerator::new(7);
+
+        let colors_a: Vec<Color> = (0..10).map(|_| a.next_color()).collect();
+        let colors_b: Vec<Color> = (0..10).map(|_| b.next_color()).collect();
+
+        assert_eq!(colors_a, colors_b);
+    }
+
+    #[test]
+    fn seed_is_exposed_as_given() {
+        let generator = PieceGenerator::new(123);
+        assert_eq!(generator.seed(), 123);
+    }
+
+    #[test]
+    fn replay_continues_the_stream_instead_of_restarting_it() {
+        let mut original = PieceGenerator::new(7);
+        let first = original.next_piece(0, 0);
+        let history_after_two = { original.next_piece(0, 0); original.history().to_vec() };
+        let third = original.next_piece(0, 0);
+
+        let mut resumed = PieceGenerator::replay(7, 4, &history_after_two);
+        let resumed_third = resumed.next_piece(0, 0);
+
+        assert_eq!(resumed_third.blocks[0].color, third.blocks[0].color);
+        assert_eq!(resumed_third.blocks[1].color, third.blocks[1].color);
+        assert_ne!(resumed_third.blocks[0].color, first.blocks[0].color);
+    }
+
+    #[test]
+    fn replay_restores_the_breaker_weight() {
+        let resumed = PieceGenerator::replay(7, 1, &[]);
+        assert_eq!(resumed.breaker_weight(), 1);
+    }
+
+    #[test]
+    fn breaker_weight_of_one_always_makes_breakers() {
+        let mut generator = PieceGenerator::with_breaker_weight(7, 1);
+        let piece = generator.next_piece(0, 0);
+
+        assert!(piece.blocks[0].breaker());
+        assert!(piece.blocks[1].breaker());
+    }
+}
@@ -0,0 +1,13 @@
+extern crate uuid;
+extern crate rand;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_derive;
+
+pub mod values;
+pub mod board;
+pub mod power_gem;
+pub mod clear;
+pub mod generator;
+#[cfg(feature = "serde")]
+pub mod state;
@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+
+use values::{Color, GridPosition, Dimension, PositionedBlock};
+
+// A solid rectangle of same-color blocks that clears as one unit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PowerGem {
+    pub color: Color,
+    pub origin: GridPosition,
+    pub size: Dimension,
+}
+
+// Finds maximal same-color rectangles (at least 2x2) among non-breaker
+// blocks, returning the gems plus the blocks left over once claimed.
+pub fn find_power_gems(field: &HashSet<PositionedBlock>, dimension: Dimension)
+    -> (Vec<PowerGem>, Vec<PositionedBlock>)
+{
+    let w = dimension.w() as usize;
+    let h = dimension.h() as usize;
+
+    let mut gems = Vec::new();
+
+    for &color in [Color::Blue, Color::Red, Color::Green, Color::Yellow].iter() {
+        let mut occupied = vec![vec![false; w]; h];
+        for block in field {
+            if block.color() == color && !block.breaker() {
+                let (x, y) = (block.x(), block.y());
+                if x >= 0 && y >= 0 && (x as usize) < w && (y as usize) < h {
+                    occupied[y as usize][x as usize] = true;
+                }
+            }
+        }
+
+        // Greedily claim the largest rectangle, mark its cells consumed, and
+        // repeat until nothing at least 2x2 remains for this color.
+        while let Some((x0, y0, rw, rh)) = largest_rectangle(&occupied, w, h) {
+            gems.push(PowerGem {
+                color: color,
+                origin: GridPosition::new(x0 as i8, y0 as i8),
+                size: Dimension::new(rw as u32, rh as u32),
+            });
+
+            for row in occupied[y0..y0 + rh].iter_mut() {
+                for cell in row[x0..x0 + rw].iter_mut() {
+                    *cell = false;
+                }
+            }
+        }
+    }
+
+    let leftover = field.iter().cloned().filter(|block| {
+        !gems.iter().any(|gem| gem_contains(gem, block))
+    }).collect();
+
+    (gems, leftover)
+}
+
+fn gem_contains(gem: &PowerGem, block: &PositionedBlock) -> bool {
+    if block.color() != gem.color {
+        return false;
+    }
+
+    let (gx, gy) = (gem.origin.x() as i32, gem.origin.y() as i32);
+    let (gw, gh) = (gem.size.w() as i32, gem.size.h() as i32);
+    let (bx, by) = (block.x() as i32, block.y() as i32);
+
+    bx >= gx && bx < gx + gw && by >= gy && by < gy + gh
+}
+
+// Per-column histogram of contiguous same-color height, scanned bottom-to-top
+// with the stack-based largest-rectangle-in-histogram technique on each row.
+// Returns the largest rectangle with area at least 4 (minimum 2x2), as
+// (x, y, width, height).
+fn largest_rectangle(occupied: &[Vec<bool>], w: usize, h: usize) -> Option<(usize, usize, usize, usize)> {
+    let mut heights = vec![0usize; w];
+    let mut best: Option<(usize, usize, usize, usize)> = None;
+    let mut best_area = 0usize;
+
+    for (y, row) in occupied.iter().enumerate().take(h) {
+        for (height, &occ) in heights.iter_mut().zip(row.iter()) {
+            *height = if occ { *height + 1 } else { 0 };
+        }
+
+        let mut stack: Vec<usize> = Vec::new();
+        for x in 0..w + 1 {
+            let cur = if x < w { heights[x] } else { 0 };
+            while let Some(&top) = stack.last() {
+                if heights[top] > cur {
+                    stack.pop();
+                    let height = heights[top];
+                    let left = match stack.last() { Some(&i) => i + 1, None => 0 };
+                    let width = x - left;
+                    let area = width * height;
+                    if width >= 2 && height >= 2 && area > best_area {
+                        best_area = area;
+                        best = Some((left, y + 1 - height, width, height));
+                    }
+                } else {
+                    break;
+                }
+            }
+            stack.push(x);
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use values::Block;
+
+    fn block(color: Color, breaker: bool, x: i8, y: i8) -> PositionedBlock {
+        PositionedBlock::new(Block::new(color, breaker), GridPosition::new(x, y))
+    }
+
+    #[test]
+    fn detects_a_single_2x2_rectangle() {
+        let mut field = HashSet::new();
+        field.insert(block(Color::Red, false, 0, 0));
+        field.insert(block(Color::Red, false, 1, 0));
+        field.insert(block(Color::Red, false, 0, 1));
+        field.insert(block(Color::Red, false, 1, 1));
+
+        let (gems, leftover) = find_power_gems(&field, Dimension::new(4, 4));
+
+        assert_eq!(gems.len(), 1);
+        assert_eq!(gems[0].color, Color::Red);
+        assert_eq!(gems[0].origin, GridPosition::new(0, 0));
+        assert_eq!(gems[0].size, Dimension::new(2, 2));
+        assert!(leftover.is_empty());
+    }
+
+    #[test]
+    fn a_single_row_or_column_is_not_a_gem() {
+        let mut field = HashSet::new();
+        field.insert(block(Color::Blue, false, 0, 0));
+        field.insert(block(Color::Blue, false, 1, 0));
+        field.insert(block(Color::Blue, false, 2, 0));
+
+        let (gems, leftover) = find_power_gems(&field, Dimension::new(4, 4));
+
+        assert!(gems.is_empty());
+        assert_eq!(leftover.len(), 3);
+    }
+
+    #[test]
+    fn breaker_blocks_never_join_a_gem() {
+        let mut field = HashSet::new();
+        field.insert(block(Color::Green, false, 0, 0));
+        field.insert(block(Color::Green, false, 1, 0));
+        field.insert(block(Color::Green, false, 0, 1));
+        field.insert(block(Color::Green, true, 1, 1));
+
+        let (gems, leftover) = find_power_gems(&field, Dimension::new(4, 4));
+
+        assert!(gems.is_empty());
+        assert_eq!(leftover.len(), 4);
+    }
+
+    #[test]
+    fn different_colors_do_not_merge_into_one_gem() {
+        let mut field = HashSet::new();
+        field.insert(block(Color::Red, false, 0, 0));
+        field.insert(block(Color::Blue, false, 1, 0));
+        field.insert(block(Color::Red, false, 0, 1));
+        field.insert(block(Color::Blue, false, 1, 1));
+
+        let (gems, leftover) = find_power_gems(&field, Dimension::new(4, 4));
+
+        assert!(gems.is_empty());
+        assert_eq!(leftover.len(), 4);
+    }
+}
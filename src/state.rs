@@ -0,0 +1,86 @@
+extern crate serde_json;
+
+use std::collections::HashSet;
+
+use values::{Piece, PositionedBlock};
+use generator::{PieceGenerator, GeneratorOp};
+
+#[derive(Serialize, Deserialize)]
+struct SavedState {
+    field: Vec<PositionedBlock>,
+    piece: Piece,
+    seed: u64,
+    breaker_weight: u32,
+    history: Vec<GeneratorOp>,
+}
+
+pub fn save_state(field: &HashSet<PositionedBlock>, piece: Piece, generator: &PieceGenerator) -> String {
+    let state = SavedState {
+        field: field.iter().cloned().collect(),
+        piece: piece,
+        seed: generator.seed(),
+        breaker_weight: generator.breaker_weight(),
+        history: generator.history().to_vec(),
+    };
+
+    serde_json::to_string(&state).unwrap()
+}
+
+pub fn load_state(json: &str) -> (HashSet<PositionedBlock>, Piece, PieceGenerator) {
+    let state: SavedState = serde_json::from_str(json).unwrap();
+    let field = state.field.into_iter().collect();
+    let generator = PieceGenerator::replay(state.seed, state.breaker_weight, &state.history);
+
+    (field, state.piece, generator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use values::{Block, Color, GridPosition};
+
+    #[test]
+    fn round_trips_field_piece_and_seed() {
+        let mut field = HashSet::new();
+        field.insert(PositionedBlock::new(Block::new(Color::Red, true), GridPosition::new(0, 0)));
+        field.insert(PositionedBlock::new(Block::new(Color::Blue, false), GridPosition::new(1, 0)));
+
+        let mut generator = PieceGenerator::new(42);
+        let piece = generator.next_piece(3, 4);
+
+        let json = save_state(&field, piece, &generator);
+        let (loaded_field, loaded_piece, loaded_generator) = load_state(&json);
+
+        assert_eq!(loaded_field, field);
+        assert_eq!(loaded_piece.position.x(), piece.position.x());
+        assert_eq!(loaded_piece.position.y(), piece.position.y());
+        assert_eq!(loaded_generator.seed(), 42);
+    }
+
+    #[test]
+    fn loaded_generator_continues_the_piece_stream_instead_of_restarting_it() {
+        let field = HashSet::new();
+
+        let mut generator = PieceGenerator::new(42);
+        let first_piece = generator.next_piece(0, 0);
+        let second_piece = generator.next_piece(0, 0);
+        let json = save_state(&field, second_piece, &generator);
+
+        let (_, _, mut loaded_generator) = load_state(&json);
+        let next_after_load = loaded_generator.next_piece(0, 0);
+
+        assert_ne!(next_after_load.blocks[0].color, first_piece.blocks[0].color);
+    }
+
+    #[test]
+    fn loaded_generator_keeps_the_custom_breaker_weight() {
+        let field = HashSet::new();
+        let mut generator = PieceGenerator::with_breaker_weight(1, 1);
+        let piece = generator.next_piece(0, 0);
+
+        let json = save_state(&field, piece, &generator);
+        let (_, _, loaded_generator) = load_state(&json);
+
+        assert_eq!(loaded_generator.breaker_weight(), 1);
+    }
+}
@@ -1,22 +1,27 @@
 extern crate uuid;
 extern crate rand;
+#[cfg(feature = "serde")]
+extern crate serde;
 
 use self::uuid::Uuid;
 
 use std::hash::{Hash, Hasher};
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct GridPosition {
     x: i8,
     y: i8,
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct PixelPosition {
     x: u32,
     y: u32,
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Dimension {
     w: u32,
@@ -74,6 +79,27 @@ impl GridPosition {
 
     pub fn x(&self) -> i8 { self.x }
     pub fn y(&self) -> i8 { self.y }
+
+    // Reduces x and y modulo the board dimension, wrapping negatives around
+    // to the far side (e.g. `-1` maps to `w - 1`) instead of going out of
+    // bounds.
+    pub fn wrapped(&self, dimension: Dimension) -> Self {
+        let w = dimension.w() as i32;
+        let h = dimension.h() as i32;
+        let x = ((self.x as i32 % w) + w) % w;
+        let y = ((self.y as i32 % h) + h) % h;
+
+        GridPosition {
+            x: x as i8,
+            y: y as i8,
+        }
+    }
+
+    // Offsets by one step and wraps the result onto the board, so a piece
+    // stepping past an edge reappears on the opposite side.
+    pub fn offset_wrapped(&self, direction: Direction, dimension: Dimension) -> Self {
+        self.offset(direction).wrapped(dimension)
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -126,6 +152,42 @@ impl Hash for Block {
     }
 }
 
+// `Uuid` doesn't derive serde's traits, so round-trip it as a string to
+// keep block identity intact across a save/load cycle.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct BlockData {
+    id: String,
+    color: Color,
+    breaker: bool,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Block {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        BlockData {
+            id: self.id.to_string(),
+            color: self.color,
+            breaker: self.breaker,
+        }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Block {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = BlockData::deserialize(deserializer)?;
+        let id = Uuid::parse_str(&data.id).map_err(serde::de::Error::custom)?;
+
+        Ok(Block {
+            id: id,
+            color: data.color,
+            breaker: data.breaker,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug)]
 pub enum Direction {
     Up,
@@ -162,6 +224,7 @@ impl Direction {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug)]
 pub struct Piece {
     // TODO: These shouldn't be public
@@ -217,6 +280,15 @@ impl Piece {
         }
     }
 
+    pub fn offset_wrapped(&self, direction: Direction, dimension: Dimension) -> Self {
+        let position = self.position.offset_wrapped(direction, dimension);
+
+        Piece {
+            position: position,
+            ..*self
+        }
+    }
+
     pub fn clockwise(&self) -> Self {
         let direction = self.direction.clockwise();
 
@@ -236,6 +308,7 @@ impl Piece {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct PositionedBlock {
     block: Block,
@@ -267,6 +340,7 @@ impl PositionedBlock {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Color {
     Blue,
@@ -289,5 +363,76 @@ impl Color {
 
         *rng.choose(&all).unwrap()
     }
+
+    // Parses a color from its board-file letter, case-insensitive.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_uppercase() {
+            'B' => Some(Color::Blue),
+            'R' => Some(Color::Red),
+            'G' => Some(Color::Green),
+            'Y' => Some(Color::Yellow),
+            _ => None,
+        }
+    }
+
+    // Renders the color as its canonical uppercase board-file letter.
+    pub fn to_char(&self) -> char {
+        match *self {
+            Color::Blue   => 'B',
+            Color::Red    => 'R',
+            Color::Green  => 'G',
+            Color::Yellow => 'Y',
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_brings_negative_coordinates_to_the_far_edge() {
+        let dimension = Dimension::new(4, 3);
+        assert_eq!(GridPosition::new(-1, 0).wrapped(dimension), GridPosition::new(3, 0));
+        assert_eq!(GridPosition::new(0, -1).wrapped(dimension), GridPosition::new(0, 2));
+    }
+
+    #[test]
+    fn wrapped_brings_out_of_bounds_coordinates_back_onto_the_board() {
+        let dimension = Dimension::new(4, 3);
+        assert_eq!(GridPosition::new(4, 0).wrapped(dimension), GridPosition::new(0, 0));
+        assert_eq!(GridPosition::new(0, 3).wrapped(dimension), GridPosition::new(0, 0));
+    }
+
+    #[test]
+    fn offset_wrapped_steps_past_an_edge_and_wraps() {
+        let dimension = Dimension::new(4, 3);
+        let position = GridPosition::new(3, 0);
+        assert_eq!(position.offset_wrapped(Direction::Right, dimension), GridPosition::new(0, 0));
+    }
+
+    #[test]
+    fn piece_offset_wrapped_wraps_its_position_only() {
+        let piece = Piece::rand(3, 0);
+        let dimension = Dimension::new(4, 3);
+        let wrapped = piece.offset_wrapped(Direction::Right, dimension);
+
+        assert_eq!(wrapped.position, GridPosition::new(0, 0));
+        assert_eq!(wrapped.direction as u8, piece.direction as u8);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn block_round_trips_identity_through_json() {
+        extern crate serde_json;
+
+        let block = Block::new(Color::Green, true);
+        let json = serde_json::to_string(&block).unwrap();
+        let restored: Block = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, block);
+        assert_eq!(restored.color, block.color);
+        assert_eq!(restored.breaker(), block.breaker());
+    }
 }
 